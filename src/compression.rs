@@ -0,0 +1,89 @@
+//! Transparent decompression support, enabled via the `compression` cargo feature.
+//!
+//! This module sniffs a file's extension and, failing that, its leading magic bytes to decide
+//! whether it's gzip- or zip-compressed, and hands back a boxed reader that transparently
+//! decodes the content. Gzip data is decoded as it's read; a zip member is decompressed into
+//! memory in full when the reader is opened.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+
+use flate2::bufread::GzDecoder;
+use zip::ZipArchive;
+
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+const ZIP_MAGIC: [u8; 4] = [0x50, 0x4B, 0x03, 0x04];
+
+enum Format {
+    Gzip,
+    Zip,
+    Plain,
+}
+
+fn sniff_format(path: &Path) -> io::Result<Format> {
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        match ext {
+            "gz" => return Ok(Format::Gzip),
+            "zip" => return Ok(Format::Zip),
+            _ => {}
+        }
+    }
+
+    let mut header = [0u8; 4];
+    let mut file = File::open(path)?;
+    let mut filled = 0;
+
+    // `read` may fill less than the whole buffer even before EOF, so keep reading until it's
+    // full or the file runs out.
+    while filled < header.len() {
+        match file.read(&mut header[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+
+    if filled >= GZIP_MAGIC.len() && header[..GZIP_MAGIC.len()] == GZIP_MAGIC {
+        Ok(Format::Gzip)
+    } else if filled >= ZIP_MAGIC.len() && header[..ZIP_MAGIC.len()] == ZIP_MAGIC {
+        Ok(Format::Zip)
+    } else {
+        Ok(Format::Plain)
+    }
+}
+
+/// Opens the file at the given path, transparently decompressing it if it's gzip- or
+/// zip-encoded.
+///
+/// A zip archive is only auto-decompressed if it contains exactly one member; otherwise this
+/// returns an `io::Error` giving the archive's member count.
+pub fn open_reader(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    match sniff_format(path)? {
+        Format::Gzip => {
+            let file = BufReader::new(File::open(path)?);
+            Ok(Box::new(BufReader::new(GzDecoder::new(file))))
+        }
+        Format::Zip => {
+            let file = File::open(path)?;
+            let mut archive = ZipArchive::new(file)?;
+
+            if archive.len() != 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!(
+                        "zip archive at {} has {} members; only single-member archives can be \
+                         auto-decompressed",
+                        path.display(),
+                        archive.len()
+                    ),
+                ));
+            }
+
+            let mut contents = Vec::new();
+            archive.by_index(0)?.read_to_end(&mut contents)?;
+
+            Ok(Box::new(Cursor::new(contents)))
+        }
+        Format::Plain => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
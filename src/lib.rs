@@ -62,6 +62,38 @@
 //!
 //! slurp::write_all_lines("myfile.txt", &content).unwrap();
 //! ```
+//!
+//! ## Moving files around
+//!
+//! When you just want to move bytes from one file to another without reading them into your own
+//! buffer, use `copy_file`, `append_file`, or `concat_files`:
+//!
+//! ```no_run
+//! slurp::copy_file("source.txt", "dest.txt").unwrap();
+//! slurp::append_file("more.txt", "dest.txt").unwrap();
+//! slurp::concat_files(vec!["a.txt", "b.txt", "c.txt"], "all.txt").unwrap();
+//! ```
+//!
+//! ## Beyond files
+//!
+//! Every function above is a thin wrapper around a `File`. If you want the same line-collection
+//! or whole-buffer logic against something else that implements `Read`/`Write` - a `TcpStream`,
+//! an in-memory `Cursor`, standard input - use the `_from`/`_to` counterparts instead:
+//!
+//! ```no_run
+//! use std::net::TcpStream;
+//!
+//! let stream = TcpStream::connect("example.com:7878").unwrap();
+//! let response = slurp::read_all_to_string_from(stream).unwrap();
+//! ```
+//!
+//! ## Compression
+//!
+//! With the `compression` feature enabled, every path-based `read_*`/`iterate_*` function
+//! transparently decompresses gzip and single-member zip files. Detection is based on the
+//! file's extension (`.gz`, `.zip`), falling back to sniffing its magic bytes, so you can point
+//! `read_all_to_string` straight at a `.gz` file and get back the decoded text. The `_from`
+//! counterparts read their reader as it is and do not decompress.
 
 #![deny(warnings, missing_docs)]
 
@@ -69,22 +101,43 @@ use std::io::{self, Read, BufRead, Write};
 use std::fs::{File, OpenOptions};
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "compression")]
+mod compression;
+
+#[cfg(feature = "compression")]
+fn open_reader<P: AsRef<Path>>(filename: P) -> io::Result<Box<dyn BufRead>> {
+    compression::open_reader(filename.as_ref())
+}
+
+#[cfg(not(feature = "compression"))]
+fn open_reader<P: AsRef<Path>>(filename: P) -> io::Result<Box<dyn BufRead>> {
+    Ok(Box::new(io::BufReader::new(File::open(filename)?)))
+}
+
 /// Reads the file at the given filename into a new String.
 pub fn read_all_to_string<P: AsRef<Path>>(filename: P) -> io::Result<String> {
+    read_all_to_string_from(open_reader(filename)?)
+}
+
+/// Reads the rest of the given reader into a new String.
+pub fn read_all_to_string_from<R: Read>(mut reader: R) -> io::Result<String> {
     let mut out = String::new();
-    let mut file = File::open(filename)?;
 
-    file.read_to_string(&mut out)?;
+    reader.read_to_string(&mut out)?;
 
     Ok(out)
 }
 
 /// Reads the file at the given filename into a new byte vector.
 pub fn read_all_bytes<P: AsRef<Path>>(filename: P) -> io::Result<Vec<u8>> {
+    read_all_bytes_from(open_reader(filename)?)
+}
+
+/// Reads the rest of the given reader into a new byte vector.
+pub fn read_all_bytes_from<R: Read>(mut reader: R) -> io::Result<Vec<u8>> {
     let mut out = Vec::new();
-    let mut file = File::open(filename)?;
 
-    file.read_to_end(&mut out)?;
+    reader.read_to_end(&mut out)?;
 
     Ok(out)
 }
@@ -105,6 +158,11 @@ pub fn read_all_lines<P: AsRef<Path>>(filename: P) -> io::Result<Vec<String>> {
     iterate_all_lines(filename).collect()
 }
 
+/// Reads the lines of the given reader into a new collection of Strings.
+pub fn read_all_lines_from<R: BufRead>(reader: R) -> io::Result<Vec<String>> {
+    reader.lines().collect()
+}
+
 /// Iterator over the lines of a file.
 ///
 /// See [`iterate_all_lines`] for details.
@@ -113,7 +171,7 @@ pub fn read_all_lines<P: AsRef<Path>>(filename: P) -> io::Result<Vec<String>> {
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct Lines {
     filename: PathBuf,
-    iter: Option<io::Lines<io::BufReader<File>>>,
+    iter: Option<io::Lines<Box<dyn BufRead>>>,
 }
 
 impl Iterator for Lines {
@@ -121,8 +179,8 @@ impl Iterator for Lines {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.iter.is_none() {
-            match File::open(&self.filename) {
-                Ok(f) => self.iter = Some(io::BufReader::new(f).lines()),
+            match open_reader(&self.filename) {
+                Ok(r) => self.iter = Some(r.lines()),
                 Err(e) => return Some(Err(e)),
             }
         }
@@ -131,25 +189,159 @@ impl Iterator for Lines {
     }
 }
 
+/// Returns an iterator over the bytes in the file at the given filename.
+///
+/// Note that this iterator lazily opens the file - it won't touch the filesystem until you start
+/// iterating.
+pub fn iterate_all_bytes<P: AsRef<Path>>(filename: P) -> Bytes {
+    Bytes {
+        filename: filename.as_ref().to_path_buf(),
+        iter: None,
+    }
+}
+
+/// Iterator over the bytes of a file.
+///
+/// See [`iterate_all_bytes`] for details.
+///
+/// [`iterate_all_bytes`]: fn.iterate_all_bytes.html
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Bytes {
+    filename: PathBuf,
+    iter: Option<io::Bytes<Box<dyn BufRead>>>,
+}
+
+impl Iterator for Bytes {
+    type Item = io::Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter.is_none() {
+            match open_reader(&self.filename) {
+                Ok(r) => self.iter = Some(r.bytes()),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        self.iter.as_mut().and_then(|i| i.next())
+    }
+}
+
+/// Returns an iterator over the chars in the file at the given filename, decoding UTF-8
+/// incrementally as it's read.
+///
+/// Note that this iterator lazily opens the file - it won't touch the filesystem until you start
+/// iterating. If the file contains invalid UTF-8, the iterator yields an `io::Error` of kind
+/// `InvalidData` at the point where decoding failed, rather than silently dropping or replacing
+/// the bad bytes.
+pub fn iterate_all_chars<P: AsRef<Path>>(filename: P) -> Chars {
+    Chars {
+        filename: filename.as_ref().to_path_buf(),
+        reader: None,
+        buf: Vec::new(),
+    }
+}
+
+/// Iterator over the chars of a file.
+///
+/// See [`iterate_all_chars`] for details.
+///
+/// [`iterate_all_chars`]: fn.iterate_all_chars.html
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Chars {
+    filename: PathBuf,
+    reader: Option<Box<dyn BufRead>>,
+    buf: Vec<u8>,
+}
+
+impl Iterator for Chars {
+    type Item = io::Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.is_none() {
+            match open_reader(&self.filename) {
+                Ok(r) => self.reader = Some(r),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        loop {
+            match std::str::from_utf8(&self.buf) {
+                Ok(s) if s.chars().next().is_some() => {
+                    return Some(Ok(self.take_char(s.chars().next().unwrap())));
+                }
+                Err(e) if e.valid_up_to() > 0 => {
+                    let s = std::str::from_utf8(&self.buf[..e.valid_up_to()]).unwrap();
+                    let c = s.chars().next().unwrap();
+                    return Some(Ok(self.take_char(c)));
+                }
+                Err(ref e) if e.error_len().is_some() => {
+                    self.buf.drain(..1);
+                    return Some(Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "invalid UTF-8 sequence",
+                    )));
+                }
+                _ => {}
+            }
+
+            let mut tmp = [0u8; 256];
+            match self.reader.as_mut().unwrap().read(&mut tmp) {
+                Ok(0) if self.buf.is_empty() => return None,
+                Ok(0) => {
+                    self.buf.clear();
+                    return Some(Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "incomplete UTF-8 sequence at end of file",
+                    )));
+                }
+                Ok(n) => self.buf.extend_from_slice(&tmp[..n]),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+impl Chars {
+    /// Removes and returns the given char from the front of `self.buf`, where `c` was just
+    /// decoded from the start of the buffered bytes.
+    fn take_char(&mut self, c: char) -> char {
+        self.buf.drain(..c.len_utf8());
+        c
+    }
+}
+
 /// Writes the given text to the file at the given filename, overwriting the file if it already
 /// exists.
 pub fn write_all_text<P: AsRef<Path>>(filename: P, text: &str) -> io::Result<()> {
     write_all_bytes(filename, text.as_bytes())
 }
 
+/// Writes the given text to the given writer.
+pub fn write_all_text_to<W: Write>(writer: W, text: &str) -> io::Result<()> {
+    write_all_bytes_to(writer, text.as_bytes())
+}
+
 /// Writes the given bytes to the file at the given filename, overwriting the file if it already
 /// exists.
 pub fn write_all_bytes<P: AsRef<Path>>(filename: P, bytes: &[u8]) -> io::Result<()> {
-    let mut file = File::create(filename)?;
+    write_all_bytes_to(File::create(filename)?, bytes)
+}
 
-    file.write_all(bytes)?;
+/// Writes the given bytes to the given writer.
+pub fn write_all_bytes_to<W: Write>(mut writer: W, bytes: &[u8]) -> io::Result<()> {
+    writer.write_all(bytes)?;
 
-    file.flush()
+    writer.flush()
 }
 
 /// Writes the given set of lines to the file at the given filename, overwriting the file if it
 /// already exists.
 ///
+/// Lines are separated with `\n`. Use [`write_all_lines_with`] to choose a different line
+/// ending.
+///
+/// [`write_all_lines_with`]: fn.write_all_lines_with.html
+///
 /// ## Errors
 ///
 /// If this function encounters an error midway through the iterator, the file will be left
@@ -161,13 +353,51 @@ pub fn write_all_lines<P: AsRef<Path>, I: IntoIterator<Item=S>, S: AsRef<str>>
 )
     -> io::Result<()>
 {
-    let mut file = File::create(filename)?;
+    write_all_lines_with(filename, lines, LineEnding::Lf)
+}
+
+/// Writes the given set of lines to the file at the given filename, overwriting the file if it
+/// already exists, separating each line with the given [`LineEnding`].
+///
+/// [`LineEnding`]: enum.LineEnding.html
+///
+/// ## Errors
+///
+/// If this function encounters an error midway through the iterator, the file will be left
+/// partially filled.
+pub fn write_all_lines_with<P: AsRef<Path>, I: IntoIterator<Item=S>, S: AsRef<str>>
+(
+    filename: P,
+    lines: I,
+    ending: LineEnding
+)
+    -> io::Result<()>
+{
+    write_all_lines_to(File::create(filename)?, lines, ending)
+}
 
+/// Writes the given set of lines to the given writer, separating each line with the given
+/// [`LineEnding`].
+///
+/// [`LineEnding`]: enum.LineEnding.html
+///
+/// ## Errors
+///
+/// If this function encounters an error midway through the iterator, the writer will be left
+/// partially filled.
+pub fn write_all_lines_to<W: Write, I: IntoIterator<Item=S>, S: AsRef<str>>
+(
+    mut writer: W,
+    lines: I,
+    ending: LineEnding
+)
+    -> io::Result<()>
+{
     for line in lines {
-        writeln!(&mut file, "{}", line.as_ref())?;
+        write!(&mut writer, "{}{}", line.as_ref(), ending.as_str())?;
     }
 
-    file.flush()
+    writer.flush()
 }
 
 /// Writes the given text to the file at the given filename, creating it if it doesn't exist or
@@ -179,16 +409,17 @@ pub fn append_all_text<P: AsRef<Path>>(filename: P, text: &str) -> io::Result<()
 /// Writes the given bytes to the file at the given filename, creating it if it doesn't exist or
 /// appending to the end if it does.
 pub fn append_all_bytes<P: AsRef<Path>>(filename: P, bytes: &[u8]) -> io::Result<()> {
-    let mut file = OpenOptions::new().create(true).append(true).open(filename)?;
-
-    file.write_all(bytes)?;
-
-    file.flush()
+    write_all_bytes_to(OpenOptions::new().create(true).append(true).open(filename)?, bytes)
 }
 
 /// Writes the given set of lines to the file at the given filename, creating it if it doesn't
 /// exist or appending to the end if it does.
 ///
+/// Lines are separated with `\n`. Use [`append_all_lines_with`] to choose a different line
+/// ending.
+///
+/// [`append_all_lines_with`]: fn.append_all_lines_with.html
+///
 /// ## Errors
 ///
 /// If this function encounters an error midway through the iterator, the file will be left
@@ -200,11 +431,188 @@ pub fn append_all_lines<P: AsRef<Path>, I: IntoIterator<Item=S>, S: AsRef<str>>
 )
     -> io::Result<()>
 {
-    let mut file = OpenOptions::new().create(true).append(true).open(filename)?;
+    append_all_lines_with(filename, lines, LineEnding::Lf)
+}
 
-    for line in lines {
-        writeln!(&mut file, "{}", line.as_ref())?;
+/// Writes the given set of lines to the file at the given filename, creating it if it doesn't
+/// exist or appending to the end if it does, separating each line with the given
+/// [`LineEnding`].
+///
+/// [`LineEnding`]: enum.LineEnding.html
+///
+/// ## Errors
+///
+/// If this function encounters an error midway through the iterator, the file will be left
+/// partially filled.
+pub fn append_all_lines_with<P: AsRef<Path>, I: IntoIterator<Item=S>, S: AsRef<str>>
+(
+    filename: P,
+    lines: I,
+    ending: LineEnding
+)
+    -> io::Result<()>
+{
+    write_all_lines_to(OpenOptions::new().create(true).append(true).open(filename)?, lines, ending)
+}
+
+/// The line ending to use when writing lines with [`write_all_lines_with`] or
+/// [`append_all_lines_with`].
+///
+/// [`write_all_lines_with`]: fn.write_all_lines_with.html
+/// [`append_all_lines_with`]: fn.append_all_lines_with.html
+///
+/// Note that reading lines back out with [`read_all_lines`] or [`iterate_all_lines`] already
+/// handles either ending transparently - both `\n` and `\r\n` are stripped from the end of each
+/// returned `String`.
+///
+/// [`read_all_lines`]: fn.read_all_lines.html
+/// [`iterate_all_lines`]: fn.iterate_all_lines.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Always separate lines with `\n`, regardless of platform.
+    Lf,
+    /// Always separate lines with `\r\n`, regardless of platform.
+    Crlf,
+    /// Separate lines with the current platform's native line ending: `\r\n` on Windows, `\n`
+    /// elsewhere.
+    Native,
+}
+
+impl LineEnding {
+    fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Native => NATIVE_LINE_ENDING,
+        }
     }
+}
+
+#[cfg(windows)]
+const NATIVE_LINE_ENDING: &str = "\r\n";
+#[cfg(not(windows))]
+const NATIVE_LINE_ENDING: &str = "\n";
+
+/// Copies the contents of the file at `src` into the file at `dst`, creating `dst` if it
+/// doesn't exist or overwriting it if it does, and returns the number of bytes copied.
+///
+/// Unlike `read_all_bytes` followed by `write_all_bytes`, this streams the data through a
+/// buffer rather than holding the whole file in memory at once.
+pub fn copy_file<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<u64> {
+    let mut source = io::BufReader::new(File::open(src)?);
+    let mut dest = File::create(dst)?;
+
+    let written = io::copy(&mut source, &mut dest)?;
+
+    dest.flush()?;
+
+    Ok(written)
+}
+
+/// Appends the contents of the file at `src` onto the end of the file at `dst`, creating `dst`
+/// if it doesn't exist, and returns the number of bytes appended.
+///
+/// Unlike `read_all_bytes` followed by `append_all_bytes`, this streams the data through a
+/// buffer rather than holding the whole file in memory at once.
+pub fn append_file<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<u64> {
+    let mut source = io::BufReader::new(File::open(src)?);
+    let mut dest = OpenOptions::new().create(true).append(true).open(dst)?;
+
+    let written = io::copy(&mut source, &mut dest)?;
+
+    dest.flush()?;
+
+    Ok(written)
+}
+
+/// Streams the contents of each file in `sources`, in order, into the file at `dst`, creating
+/// `dst` if it doesn't exist or overwriting it if it does, and returns the total number of bytes
+/// written.
+pub fn concat_files<P: AsRef<Path>, Q: AsRef<Path>, I: IntoIterator<Item=P>>
+(
+    sources: I,
+    dst: Q
+)
+    -> io::Result<u64>
+{
+    let mut dest = File::create(dst)?;
+    let mut written = 0;
+
+    for src in sources {
+        let mut source = io::BufReader::new(File::open(src)?);
 
-    file.flush()
+        written += io::copy(&mut source, &mut dest)?;
+    }
+
+    dest.flush()?;
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::iterate_all_chars;
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("slurp-test-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn chars_spanning_a_read_boundary() {
+        let path = temp_path("chars-boundary.txt");
+        let mut bytes = vec![b'a'; 255];
+        bytes.extend_from_slice("\u{1F389}".as_bytes());
+        bytes.extend_from_slice(b"bc");
+        fs::write(&path, &bytes).unwrap();
+
+        let chars: Vec<char> = iterate_all_chars(&path).map(|c| c.unwrap()).collect();
+        let expected: String = "a".repeat(255) + "\u{1F389}bc";
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(chars.into_iter().collect::<String>(), expected);
+    }
+
+    #[test]
+    fn chars_invalid_byte_mid_stream() {
+        let path = temp_path("chars-invalid.txt");
+        fs::write(&path, [b'a', b'b', 0xFF, b'c', b'd']).unwrap();
+
+        let results: Vec<_> = iterate_all_chars(&path).collect();
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(results[0].as_ref().unwrap(), &'a');
+        assert_eq!(results[1].as_ref().unwrap(), &'b');
+        assert_eq!(
+            results[2].as_ref().unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+        assert_eq!(results[3].as_ref().unwrap(), &'c');
+        assert_eq!(results[4].as_ref().unwrap(), &'d');
+        assert_eq!(results.len(), 5);
+    }
+
+    #[test]
+    fn chars_truncated_trailing_sequence() {
+        let path = temp_path("chars-truncated.txt");
+        // 0xE2 0x82 is the start of a three-byte sequence (e.g. the Euro sign) with the final
+        // byte missing.
+        fs::write(&path, [b'a', b'b', 0xE2, 0x82]).unwrap();
+
+        let mut iter = iterate_all_chars(&path);
+        let first = iter.next().unwrap().unwrap();
+        let second = iter.next().unwrap().unwrap();
+        let err = iter.next().unwrap().unwrap_err();
+        let done = iter.next();
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(first, 'a');
+        assert_eq!(second, 'b');
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(done.is_none());
+    }
 }